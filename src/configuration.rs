@@ -0,0 +1,80 @@
+use secrecy::Secret;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+use crate::domain::SubscriberEmail;
+use crate::email_client::EmailClient;
+
+fn default_worker_concurrency() -> usize {
+    10
+}
+
+fn default_max_in_flight_deliveries() -> usize {
+    10
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct Settings {
+    pub database: DatabaseSettings,
+    pub application: ApplicationSettings,
+    pub email_client: EmailClientSettings,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct ApplicationSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub port: u16,
+    pub host: String,
+    pub base_url: String,
+    pub hmac_secret: Secret<String>,
+    /// How many `issue_delivery_worker::worker_loop` tasks to run
+    /// concurrently. `SKIP LOCKED` in `dequeue_task` guarantees each one
+    /// claims a distinct row.
+    #[serde(default = "default_worker_concurrency")]
+    pub worker_concurrency: usize,
+    /// Upper bound on deliveries in flight at once across *all* workers,
+    /// independent of `worker_concurrency`, so the email provider's rate
+    /// limit is respected regardless of how many workers are running.
+    #[serde(default = "default_max_in_flight_deliveries")]
+    pub max_in_flight_deliveries: usize,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct EmailClientSettings {
+    pub base_url: String,
+    pub sender_email: String,
+    pub authorization_token: Secret<String>,
+    pub timeout_milliseconds: u64,
+}
+
+impl EmailClientSettings {
+    pub fn sender(&self) -> Result<SubscriberEmail, String> {
+        SubscriberEmail::parse(self.sender_email.clone())
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.timeout_milliseconds)
+    }
+
+    pub fn client(&self) -> EmailClient {
+        let sender_email = self
+            .sender()
+            .expect("Invalid sender email address in configuration.");
+        EmailClient::new(
+            self.base_url.clone(),
+            sender_email,
+            self.authorization_token.clone(),
+            self.timeout(),
+        )
+    }
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct DatabaseSettings {
+    pub username: String,
+    pub password: Secret<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub port: u16,
+    pub host: String,
+    pub database_name: String,
+    pub require_ssl: bool,
+}
@@ -0,0 +1,52 @@
+#[derive(Debug)]
+pub struct IdempotencyKey(String);
+
+impl TryFrom<String> for IdempotencyKey {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        if s.is_empty() {
+            anyhow::bail!("The idempotency key cannot be empty");
+        }
+        let max_length = 50;
+        if s.len() >= max_length {
+            anyhow::bail!("The idempotency key must be shorter than {max_length} characters");
+        }
+        Ok(Self(s))
+    }
+}
+
+impl From<IdempotencyKey> for String {
+    fn from(k: IdempotencyKey) -> Self {
+        k.0
+    }
+}
+
+impl AsRef<str> for IdempotencyKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdempotencyKey;
+
+    #[test]
+    fn empty_key_is_rejected() {
+        assert!(IdempotencyKey::try_from("".to_string()).is_err());
+    }
+
+    #[test]
+    fn key_at_the_length_limit_is_rejected() {
+        let key = "a".repeat(50);
+        assert!(IdempotencyKey::try_from(key).is_err());
+    }
+
+    #[test]
+    fn a_valid_key_round_trips() {
+        let key = IdempotencyKey::try_from("a-valid-key".to_string()).unwrap();
+        assert_eq!(key.as_ref(), "a-valid-key");
+        assert_eq!(String::from(key), "a-valid-key");
+    }
+}
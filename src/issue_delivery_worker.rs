@@ -1,13 +1,19 @@
 use crate::configuration::Settings;
+use crate::domain::SubscriberEmail;
 use crate::email_client::EmailClient;
 use crate::startup::get_connection_pool;
-use crate::domain::SubscriberEmail;
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::time::{sleep, Duration};
 use tracing::{field::display, Span};
 use uuid::Uuid;
 
 const MAX_RETRIES: i16 = 5;
+const BASE_BACKOFF: i64 = 3;
+const MAX_BACKOFF: i64 = 3600;
 
 pub enum ExecutionOutcome {
     TaskCompleted,
@@ -26,29 +32,29 @@ pub enum ExecutionOutcome {
 pub async fn try_execute_task(
     pool: &PgPool,
     email_client: &EmailClient,
+    send_permits: &Semaphore,
 ) -> Result<ExecutionOutcome, anyhow::Error> {
+    // Acquired before `dequeue_task` opens its row-locked transaction, so a
+    // worker never holds a `FOR UPDATE` row lock (and the pool connection
+    // backing it) while merely waiting for a send slot: connections in use
+    // for in-flight deliveries are bounded by `send_permits` alone, not by
+    // `2 * worker_concurrency`.
+    let _permit = send_permits.acquire().await?;
+
     let task = dequeue_task(pool).await?;
     if task.is_none() {
         return Ok(ExecutionOutcome::EmptyQueue);
     }
-    let (transaction, issue_id, email, n_retries, execute_after) = task.unwrap();
+    let (transaction, issue_id, email, n_retries) = task.unwrap();
     {
-        if n_retries == MAX_RETRIES {
-            delete_task(transaction, issue_id, &email).await?;
-            return Err(anyhow::anyhow!(
-                "Failed to deliver issue to a confirmed subscriber. \
-                Skipping this subscriber."
-            ));
-        };
         Span::current()
             .record("newsletter_issue_id", &display(issue_id))
             .record("subscriber_email", &display(&email))
             .record("retries", &display(&n_retries));
-        sleep(Duration::from_secs(execute_after as u64)).await;
         match SubscriberEmail::parse(email.clone()) {
             Ok(email) => {
                 let issue = get_issue(pool, issue_id).await?;
-                if let Err(e) = email_client
+                match email_client
                     .send_email(
                         &email,
                         &issue.title,
@@ -57,21 +63,37 @@ pub async fn try_execute_task(
                     )
                     .await
                 {
-                    delete_task(transaction, issue_id, &email.to_string()).await?;
-                    requeue_task(
-                        pool,
-                        issue_id,
-                        &email.to_string(),
-                        n_retries + 1,
-                        execute_after + 1,
-                    )
-                    .await?;
-                    tracing::error!(
-                        error.cause_chain = ?e,
-                        error.message = %e,
-                        "Failed to deliver issue to a confirmed subscriber. \
-                        Adding back to the queue.",
-                    );
+                    Ok(()) => {
+                        delete_task(transaction, issue_id, &email.to_string()).await?;
+                    }
+                    Err(e) => {
+                        let n_retries = n_retries + 1;
+                        if n_retries >= MAX_RETRIES {
+                            record_failure(
+                                transaction,
+                                issue_id,
+                                &email.to_string(),
+                                n_retries,
+                                &e.to_string(),
+                            )
+                            .await?;
+                            tracing::error!(
+                                error.cause_chain = ?e,
+                                error.message = %e,
+                                "Failed to deliver issue to a confirmed subscriber. \
+                                Retries exhausted, recording to the dead-letter table.",
+                            );
+                        } else {
+                            delete_task(transaction, issue_id, &email.to_string()).await?;
+                            requeue_task(pool, issue_id, &email.to_string(), n_retries).await?;
+                            tracing::error!(
+                                error.cause_chain = ?e,
+                                error.message = %e,
+                                "Failed to deliver issue to a confirmed subscriber. \
+                                Adding back to the queue.",
+                            );
+                        }
+                    }
                 }
             }
             Err(e) => {
@@ -93,12 +115,13 @@ type PgTransaction = Transaction<'static, Postgres>;
 #[tracing::instrument(skip_all)]
 async fn dequeue_task(
     pool: &PgPool,
-) -> Result<Option<(PgTransaction, Uuid, String, i16, i16)>, anyhow::Error> {
+) -> Result<Option<(PgTransaction, Uuid, String, i16)>, anyhow::Error> {
     let mut transaction = pool.begin().await?;
     let r = sqlx::query!(
         r#"
-        SELECT newsletter_issue_id, subscriber_email, n_retries, execute_after
+        SELECT newsletter_issue_id, subscriber_email, n_retries
         FROM issue_delivery_queue
+        WHERE execute_after <= now()
         FOR UPDATE
         SKIP LOCKED
         LIMIT 1
@@ -112,7 +135,6 @@ async fn dequeue_task(
             r.newsletter_issue_id,
             r.subscriber_email,
             r.n_retries,
-            r.execute_after,
         )))
     } else {
         Ok(None)
@@ -141,6 +163,47 @@ async fn delete_task(
     Ok(())
 }
 
+#[tracing::instrument(skip_all)]
+async fn record_failure(
+    mut transaction: PgTransaction,
+    issue_id: Uuid,
+    email: &str,
+    n_retries: i16,
+    last_error: &str,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM issue_delivery_queue
+        WHERE
+            newsletter_issue_id = $1 AND
+            subscriber_email = $2
+        "#,
+        issue_id,
+        email
+    )
+    .execute(&mut transaction)
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO newsletter_delivery_failures (
+            newsletter_issue_id,
+            subscriber_email,
+            n_retries,
+            last_error
+        )
+        VALUES ($1, $2, $3, $4)
+        "#,
+        issue_id,
+        email,
+        n_retries,
+        last_error
+    )
+    .execute(&mut transaction)
+    .await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
 struct NewsletterIssue {
     title: String,
     text_content: String,
@@ -164,13 +227,26 @@ async fn get_issue(pool: &PgPool, issue_id: Uuid) -> Result<NewsletterIssue, any
     Ok(issue)
 }
 
+/// Exponential backoff with jitter: `base * 2^n_retries`, capped at
+/// `MAX_BACKOFF` seconds, plus a random fraction of `[0, delay/2)` so that
+/// subscribers whose emails fail together don't all retry in lockstep
+/// against the email provider.
+fn next_execution_time(n_retries: i16) -> DateTime<Utc> {
+    let exponent = n_retries.clamp(0, 32) as u32;
+    let delay_seconds = BASE_BACKOFF
+        .saturating_mul(1i64.checked_shl(exponent).unwrap_or(i64::MAX))
+        .min(MAX_BACKOFF);
+    let jitter_seconds = rand::thread_rng().gen_range(0.0..(delay_seconds as f64 / 2.0));
+    Utc::now() + chrono::Duration::milliseconds(((delay_seconds as f64 + jitter_seconds) * 1000.0) as i64)
+}
+
 async fn requeue_task(
     pool: &PgPool,
     issue_id: Uuid,
     email: &str,
     n_retries: i16,
-    execute_after: i16,
 ) -> Result<(), anyhow::Error> {
+    let execute_after = next_execution_time(n_retries);
     sqlx::query!(
         r#"
         INSERT INTO issue_delivery_queue (
@@ -196,9 +272,13 @@ async fn requeue_task(
     Ok(())
 }
 
-async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyhow::Error> {
+async fn worker_loop(
+    pool: PgPool,
+    email_client: EmailClient,
+    send_permits: Arc<Semaphore>,
+) -> Result<(), anyhow::Error> {
     loop {
-        match try_execute_task(&pool, &email_client).await {
+        match try_execute_task(&pool, &email_client, &send_permits).await {
             Ok(ExecutionOutcome::EmptyQueue) => sleep(Duration::from_secs(10)).await,
             Err(_) => sleep(Duration::from_secs(1)).await,
             Ok(ExecutionOutcome::TaskCompleted) => {}
@@ -206,9 +286,56 @@ async fn worker_loop(pool: PgPool, email_client: EmailClient) -> Result<(), anyh
     }
 }
 
+/// Spawns `configuration.application.worker_concurrency` independent
+/// `worker_loop`s sharing a single pool and email client: `SKIP LOCKED`
+/// in `dequeue_task` already guarantees each one grabs a distinct row, so
+/// this scales delivery throughput without any coordination between them.
+/// `send_permits` additionally caps how many deliveries are in flight at
+/// once across *all* workers, independent of how many workers are running,
+/// so the provider's rate limit is respected regardless of concurrency.
 pub async fn run_worker_until_stopped(configuration: Settings) -> Result<(), anyhow::Error> {
     let connection_pool = get_connection_pool(&configuration.database);
-
     let email_client = configuration.email_client.client();
-    worker_loop(connection_pool, email_client).await
+    let send_permits = Arc::new(Semaphore::new(
+        configuration.application.max_in_flight_deliveries,
+    ));
+
+    let workers: Vec<_> = (0..configuration.application.worker_concurrency)
+        .map(|_| {
+            tokio::spawn(worker_loop(
+                connection_pool.clone(),
+                email_client.clone(),
+                Arc::clone(&send_permits),
+            ))
+        })
+        .collect();
+
+    for outcome in futures::future::try_join_all(workers).await? {
+        outcome?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_execution_time, BASE_BACKOFF, MAX_BACKOFF};
+    use chrono::Utc;
+
+    #[test]
+    fn backoff_is_in_the_future_and_grows_with_retries() {
+        let now = Utc::now();
+        let first = next_execution_time(0);
+        let later = next_execution_time(4);
+        assert!(first > now);
+        assert!(later > first);
+    }
+
+    #[test]
+    fn backoff_is_capped_and_jitter_never_doubles_the_cap() {
+        let now = Utc::now();
+        let delay = next_execution_time(32) - now;
+        // Cap plus the largest possible jitter (half the cap).
+        assert!(delay.num_seconds() <= MAX_BACKOFF + MAX_BACKOFF / 2 + 1);
+        assert!(delay.num_seconds() >= BASE_BACKOFF);
+    }
 }
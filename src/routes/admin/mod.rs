@@ -0,0 +1,11 @@
+pub mod newsletter_failures;
+
+use actix_web::web;
+
+/// Registers the admin routes added alongside `newsletter_failures`. Other
+/// `admin/*` submodules (password change, dashboard) register themselves
+/// the same way from the app factory in `startup.rs`:
+/// `web::scope("/admin").configure(routes::admin::configure)`.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    newsletter_failures::configure(cfg);
+}
@@ -0,0 +1,137 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::{e500, see_other};
+
+struct DeliveryFailure {
+    subscriber_email: String,
+    n_retries: i16,
+    last_error: String,
+}
+
+#[tracing::instrument(name = "List newsletter delivery failures", skip(pool))]
+pub async fn list_failures(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let newsletter_issue_id = path.into_inner();
+    let failures = sqlx::query_as!(
+        DeliveryFailure,
+        r#"
+        SELECT subscriber_email, n_retries, last_error
+        FROM newsletter_delivery_failures
+        WHERE newsletter_issue_id = $1
+        ORDER BY failed_at
+        "#,
+        newsletter_issue_id
+    )
+    .fetch_all(pool.get_ref())
+    .await
+    .map_err(e500)?;
+
+    let html = render_failures_page(newsletter_issue_id, &failures);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
+
+fn render_failures_page(newsletter_issue_id: Uuid, failures: &[DeliveryFailure]) -> String {
+    let mut rows = String::new();
+    for failure in failures {
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            htmlescape::encode_minimal(&failure.subscriber_email),
+            failure.n_retries,
+            htmlescape::encode_minimal(&failure.last_error)
+        ));
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="utf-8"><title>Delivery failures</title></head>
+<body>
+<table>
+<tr><th>Subscriber</th><th>Retries</th><th>Last error</th></tr>
+{rows}
+</table>
+<form method="post" action="/admin/newsletters/{newsletter_issue_id}/failures/retry">
+<button type="submit">Retry failed deliveries</button>
+</form>
+</body>
+</html>"#
+    )
+}
+
+#[tracing::instrument(name = "Retry failed newsletter deliveries", skip(pool))]
+pub async fn retry_failures(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let newsletter_issue_id = path.into_inner();
+    let mut transaction = pool.begin().await.map_err(e500)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO issue_delivery_queue (newsletter_issue_id, subscriber_email, n_retries, execute_after)
+        SELECT newsletter_issue_id, subscriber_email, 0, now()
+        FROM newsletter_delivery_failures
+        WHERE newsletter_issue_id = $1
+        "#,
+        newsletter_issue_id
+    )
+    .execute(&mut transaction)
+    .await
+    .map_err(e500)?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM newsletter_delivery_failures
+        WHERE newsletter_issue_id = $1
+        "#,
+        newsletter_issue_id
+    )
+    .execute(&mut transaction)
+    .await
+    .map_err(e500)?;
+
+    transaction.commit().await.map_err(e500)?;
+
+    Ok(see_other(&format!(
+        "/admin/newsletters/{newsletter_issue_id}/failures"
+    )))
+}
+
+/// Registers this module's routes onto the admin scope. Call from the
+/// admin scope builder in `startup.rs`:
+/// `web::scope("/admin").configure(routes::admin::newsletter_failures::configure)`.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route(
+        "/newsletters/{newsletter_issue_id}/failures",
+        web::get().to(list_failures),
+    )
+    .route(
+        "/newsletters/{newsletter_issue_id}/failures/retry",
+        web::post().to(retry_failures),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_failures_page, DeliveryFailure};
+    use uuid::Uuid;
+
+    #[test]
+    fn subscriber_email_and_last_error_are_html_escaped() {
+        let failures = vec![DeliveryFailure {
+            subscriber_email: "<script>alert('xss')</script>@example.com".to_string(),
+            n_retries: 5,
+            last_error: "timed out & <b>retried</b>".to_string(),
+        }];
+        let html = render_failures_page(Uuid::new_v4(), &failures);
+
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("timed out & <b>retried</b>"));
+        assert!(html.contains("&amp;"));
+        assert!(html.contains("&lt;b&gt;"));
+    }
+}
@@ -1,44 +1,123 @@
 use actix_web::{web, HttpResponse, ResponseError};
-use sqlx::PgPool;
-use crate::routes::error_chain_fmt;
 use actix_web::http::StatusCode;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
 
-struct ConfirmedSubscriber {
-    email: String,
-}
-
-#[tracing::instrument(name = "Get confirmed subscribers", skip(pool))]
-async fn get_confirmed_subscribers(
-    pool: &PgPool,
-) -> Result<Vec<ConfirmedSubscriber>, anyhow::Error> {
-    let rows = sqlx::query_as!(
-        ConfirmedSubscriber,
-        r#"
-        SELECT email
-        FROM subscriptions
-        WHERE status = 'confirmed'
-        "#
-    )
-    .fetch_all(pool)
-    .await?;
-
-    Ok(rows)
-}
+use crate::authentication::UserId;
+use crate::idempotency::{get_saved_response, save_response, IdempotencyKey};
+use crate::routes::error_chain_fmt;
 
 #[derive(serde::Deserialize)]
 pub struct BodyData {
     title: String,
     content: Content,
+    idempotency_key: String,
 }
 #[derive(serde::Deserialize)]
 pub struct Content {
     html: String,
     text: String,
 }
+
+#[derive(thiserror::Error)]
+pub enum PublishError {
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl std::fmt::Debug for PublishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for PublishError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PublishError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[tracing::instrument(
+    name = "Publish a newsletter issue",
+    skip(body, pool),
+    fields(user_id=%*user_id)
+)]
 pub async fn publish_newsletter(
-    _body: web::Json<BodyData>,
+    body: web::Json<BodyData>,
     pool: web::Data<PgPool>,
-) -> HttpResponse {
-    let _confirmed_subscribers = get_confirmed_subscribers(&pool).await?;
-    HttpResponse::Ok().finish()
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, PublishError> {
+    let user_id = user_id.into_inner();
+    let idempotency_key: IdempotencyKey = body.0.idempotency_key.clone().try_into()?;
+
+    if let Some(saved_response) = get_saved_response(&pool, &idempotency_key, *user_id).await? {
+        return Ok(saved_response);
+    }
+
+    let mut transaction = pool.begin().await.map_err(anyhow::Error::from)?;
+    let issue_id = insert_newsletter_issue(
+        &mut transaction,
+        &body.0.title,
+        &body.0.content.text,
+        &body.0.content.html,
+    )
+    .await?;
+    enqueue_delivery_tasks(&mut transaction, issue_id).await?;
+
+    let response = HttpResponse::Ok().finish();
+    let response = save_response(&pool, transaction, &idempotency_key, *user_id, response).await?;
+    Ok(response)
+}
+
+#[tracing::instrument(skip_all)]
+async fn insert_newsletter_issue(
+    transaction: &mut Transaction<'_, Postgres>,
+    title: &str,
+    text_content: &str,
+    html_content: &str,
+) -> Result<Uuid, anyhow::Error> {
+    let newsletter_issue_id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO newsletter_issues (
+            newsletter_issue_id,
+            title,
+            text_content,
+            html_content,
+            published_at
+        )
+        VALUES ($1, $2, $3, $4, now())
+        "#,
+        newsletter_issue_id,
+        title,
+        text_content,
+        html_content
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(newsletter_issue_id)
+}
+
+#[tracing::instrument(skip_all)]
+async fn enqueue_delivery_tasks(
+    transaction: &mut Transaction<'_, Postgres>,
+    newsletter_issue_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO issue_delivery_queue (
+            newsletter_issue_id,
+            subscriber_email
+        )
+        SELECT $1, email
+        FROM subscriptions
+        WHERE status = 'confirmed'
+        "#,
+        newsletter_issue_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
 }
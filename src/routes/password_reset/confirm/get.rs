@@ -0,0 +1,42 @@
+use actix_web::http::header::ContentType;
+use actix_web::{web, HttpResponse};
+use actix_web_flash_messages::IncomingFlashMessages;
+use std::fmt::Write;
+
+#[derive(serde::Deserialize)]
+pub struct QueryParams {
+    token: String,
+}
+
+#[tracing::instrument(name = "Render the password reset confirmation form", skip(flash_messages))]
+pub async fn confirm_password_reset_form(
+    query: web::Query<QueryParams>,
+    flash_messages: IncomingFlashMessages,
+) -> HttpResponse {
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "<p><i>{}</i></p>", m.content()).unwrap();
+    }
+    let token = htmlescape::encode_minimal(&query.token);
+    HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="utf-8"><title>Choose a new password</title></head>
+<body>
+{msg_html}
+<form method="post" action="/password_reset/confirm">
+<input type="hidden" name="token" value="{token}">
+<label>New password
+    <input type="password" name="new_password">
+</label>
+<label>Confirm new password
+    <input type="password" name="new_password_check">
+</label>
+<button type="submit">Reset password</button>
+</form>
+</body>
+</html>"#
+        ))
+}
@@ -0,0 +1,5 @@
+mod get;
+mod post;
+
+pub use get::confirm_password_reset_form;
+pub use post::confirm_password_reset;
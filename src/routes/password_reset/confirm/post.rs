@@ -0,0 +1,123 @@
+use actix_web::{web, HttpResponse};
+use actix_web_flash_messages::FlashMessage;
+use chrono::Utc;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use secrecy::{ExposeSecret, Secret};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::{e500, see_other};
+
+#[derive(serde::Deserialize)]
+pub struct FormData {
+    token: String,
+    new_password: Secret<String>,
+    new_password_check: Secret<String>,
+}
+
+#[tracing::instrument(name = "Confirm a password reset", skip(form, pool))]
+pub async fn confirm_password_reset(
+    form: web::Form<FormData>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse, actix_web::Error> {
+    if form.new_password.expose_secret() != form.new_password_check.expose_secret() {
+        FlashMessage::error(
+            "You entered two different new passwords - the field values must match.",
+        )
+        .send();
+        return Ok(redirect_to_confirm_form(&form.token));
+    }
+    if form.new_password.expose_secret().len() < 12 {
+        FlashMessage::error(
+            "Your password is too short - Passwords must be at least 12 characters.",
+        )
+        .send();
+        return Ok(redirect_to_confirm_form(&form.token));
+    }
+    if form.new_password.expose_secret().len() > 128 {
+        FlashMessage::error(
+            "Your password is too long - Passwords must be at most 128 characters.",
+        )
+        .send();
+        return Ok(redirect_to_confirm_form(&form.token));
+    }
+
+    let user_id = match get_user_id_for_token(&pool, &form.token).await.map_err(e500)? {
+        Some(user_id) => user_id,
+        None => {
+            FlashMessage::error("That password reset link is invalid or has expired.").send();
+            return Ok(see_other("/password_reset"));
+        }
+    };
+
+    crate::authentication::change_password(user_id, form.0.new_password, &pool)
+        .await
+        .map_err(e500)?;
+    invalidate_token(&pool, &form.token).await.map_err(e500)?;
+
+    FlashMessage::info("Your password has been reset - you can now log in.").send();
+    Ok(see_other("/login"))
+}
+
+fn redirect_to_confirm_form(token: &str) -> HttpResponse {
+    let token = utf8_percent_encode(token, NON_ALPHANUMERIC);
+    see_other(&format!("/password_reset/confirm?token={token}"))
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+async fn get_user_id_for_token(pool: &PgPool, token: &str) -> Result<Option<Uuid>, anyhow::Error> {
+    let token_hash = hash_token(token);
+    let row = sqlx::query!(
+        r#"
+        SELECT user_id, expires_at
+        FROM password_reset_tokens
+        WHERE token_hash = $1
+        "#,
+        token_hash
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.filter(|r| r.expires_at > Utc::now()).map(|r| r.user_id))
+}
+
+async fn invalidate_token(pool: &PgPool, token: &str) -> Result<(), anyhow::Error> {
+    let token_hash = hash_token(token);
+    sqlx::query!(
+        "DELETE FROM password_reset_tokens WHERE token_hash = $1",
+        token_hash
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_token, redirect_to_confirm_form};
+
+    #[test]
+    fn hash_token_is_deterministic_and_not_the_raw_token() {
+        let token = "a-raw-token";
+        assert_eq!(hash_token(token), hash_token(token));
+        assert_ne!(hash_token(token), token);
+    }
+
+    #[test]
+    fn redirect_percent_encodes_a_token_with_special_characters() {
+        let response = redirect_to_confirm_form("abc&def#ghi\r\n");
+        let location = response
+            .headers()
+            .get("Location")
+            .expect("redirect has a Location header")
+            .to_str()
+            .unwrap();
+        assert!(!location.contains('&'));
+        assert!(!location.contains('#'));
+        assert!(!location.contains('\r'));
+        assert!(!location.contains('\n'));
+    }
+}
@@ -0,0 +1,23 @@
+mod confirm;
+mod request;
+
+pub use confirm::{confirm_password_reset, confirm_password_reset_form};
+pub use request::{password_reset_form, request_password_reset};
+
+use actix_web::web;
+
+/// Registers the forgotten-password subsystem's public (unauthenticated)
+/// routes. Call from the app factory in `startup.rs`:
+/// `.configure(routes::password_reset::configure)`.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/password_reset", web::get().to(password_reset_form))
+        .route("/password_reset", web::post().to(request_password_reset))
+        .route(
+            "/password_reset/confirm",
+            web::get().to(confirm_password_reset_form),
+        )
+        .route(
+            "/password_reset/confirm",
+            web::post().to(confirm_password_reset),
+        );
+}
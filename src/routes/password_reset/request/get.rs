@@ -0,0 +1,29 @@
+use actix_web::http::header::ContentType;
+use actix_web::HttpResponse;
+use actix_web_flash_messages::IncomingFlashMessages;
+use std::fmt::Write;
+
+#[tracing::instrument(name = "Render the password reset request form", skip(flash_messages))]
+pub async fn password_reset_form(flash_messages: IncomingFlashMessages) -> HttpResponse {
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "<p><i>{}</i></p>", m.content()).unwrap();
+    }
+    HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="utf-8"><title>Reset your password</title></head>
+<body>
+{msg_html}
+<form method="post" action="/password_reset">
+<label>Email
+    <input type="email" name="email" placeholder="you@example.com">
+</label>
+<button type="submit">Send reset instructions</button>
+</form>
+</body>
+</html>"#
+        ))
+}
@@ -0,0 +1,136 @@
+use actix_web::{web, HttpResponse};
+use actix_web_flash_messages::FlashMessage;
+use chrono::{Duration, Utc};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::email_client::EmailClient;
+use crate::startup::ApplicationBaseUrl;
+use crate::utils::{e500, see_other};
+
+#[derive(serde::Deserialize)]
+pub struct FormData {
+    email: String,
+}
+
+const TOKEN_TTL_HOURS: i64 = 1;
+
+#[tracing::instrument(name = "Request a password reset", skip(form, pool, email_client))]
+pub async fn request_password_reset(
+    form: web::Form<FormData>,
+    pool: web::Data<PgPool>,
+    email_client: web::Data<EmailClient>,
+    base_url: web::Data<ApplicationBaseUrl>,
+) -> Result<HttpResponse, actix_web::Error> {
+    if let Some(user_id) = get_user_id_by_email(&pool, &form.email).await.map_err(e500)? {
+        let token = generate_reset_token();
+        // Log-and-swallow: a DB hiccup, a legacy address `SubscriberEmail`
+        // rejects, or a provider error here must not surface as a 500,
+        // or the status code alone would reveal that the account exists.
+        if let Err(e) = store_token(&pool, user_id, &token).await {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to store a password reset token."
+            );
+        } else if let Err(e) = send_reset_email(&email_client, &form.email, &base_url.0, &token).await
+        {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to send a password reset email."
+            );
+        }
+    }
+
+    // Always respond the same way, whether or not the address is on file,
+    // so this endpoint can't be used to enumerate registered accounts.
+    FlashMessage::info(
+        "If that email address is on file, we've sent instructions to reset your password.",
+    )
+    .send();
+    Ok(see_other("/password_reset"))
+}
+
+async fn get_user_id_by_email(pool: &PgPool, email: &str) -> Result<Option<Uuid>, anyhow::Error> {
+    let row = sqlx::query!("SELECT user_id FROM users WHERE email = $1", email)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|r| r.user_id))
+}
+
+fn generate_reset_token() -> String {
+    rand::thread_rng()
+        .sample_iter(Alphanumeric)
+        .map(char::from)
+        .take(48)
+        .collect()
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+async fn store_token(pool: &PgPool, user_id: Uuid, token: &str) -> Result<(), anyhow::Error> {
+    let token_hash = hash_token(token);
+    let expires_at = Utc::now() + Duration::hours(TOKEN_TTL_HOURS);
+    sqlx::query!(
+        r#"
+        INSERT INTO password_reset_tokens (token_hash, user_id, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+        token_hash,
+        user_id,
+        expires_at
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn send_reset_email(
+    email_client: &EmailClient,
+    recipient: &str,
+    base_url: &str,
+    token: &str,
+) -> Result<(), anyhow::Error> {
+    let reset_link = format!("{base_url}/password_reset/confirm?token={token}");
+    let html_body = format!(
+        "Click <a href=\"{reset_link}\">here</a> to reset your password. \
+        This link expires in {TOKEN_TTL_HOURS} hour(s)."
+    );
+    let text_body = format!(
+        "Visit {reset_link} to reset your password. \
+        This link expires in {TOKEN_TTL_HOURS} hour(s)."
+    );
+    let recipient = crate::domain::SubscriberEmail::parse(recipient.to_owned())?;
+    email_client
+        .send_email(&recipient, "Reset your password", &html_body, &text_body)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_reset_token, hash_token};
+    use std::collections::HashSet;
+
+    #[test]
+    fn generated_tokens_are_unique_and_alphanumeric() {
+        let tokens: HashSet<String> = (0..100).map(|_| generate_reset_token()).collect();
+        assert_eq!(tokens.len(), 100);
+        assert!(tokens
+            .iter()
+            .all(|t| t.len() == 48 && t.chars().all(|c| c.is_ascii_alphanumeric())));
+    }
+
+    #[test]
+    fn hashing_a_token_does_not_reveal_it() {
+        let token = generate_reset_token();
+        let hashed = hash_token(&token);
+        assert_ne!(token, hashed);
+        assert_eq!(hashed, hash_token(&token));
+    }
+}